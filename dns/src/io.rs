@@ -0,0 +1,87 @@
+//! A minimal byte I/O abstraction that the wire layer is built on, so it
+//! can run in `no_std` + `alloc` environments (embedded or sandboxed
+//! resolvers) as well as on top of `std`.
+//!
+//! With the `std` feature enabled (the default) this is a thin re-export
+//! of `std::io`. Without it, this module supplies just enough of
+//! `Read`/`Write`/`Cursor`/`Error` — backed only by `core` and `alloc` —
+//! for `Wire::read` and `Wire::write` to be implemented against either one
+//! interchangeably. The crate root is expected to gate itself with
+//! `#![cfg_attr(not(feature = "std"), no_std)]` and `extern crate alloc;`
+//! when the `std` feature is off.
+
+#[cfg(feature = "std")]
+mod imp {
+    pub use std::io::{Cursor, Error, Read, Result, Write};
+}
+
+#[cfg(not(feature = "std"))]
+mod imp {
+    use alloc::vec::Vec;
+    use core::fmt;
+
+    /// A minimal stand-in for `std::io::Error`. The wire layer only ever
+    /// reports one failure mode (running out of bytes), so unlike its
+    /// `std` counterpart this carries no further detail.
+    #[derive(Debug)]
+    pub struct Error;
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "IO error")
+        }
+    }
+
+    /// A minimal stand-in for `std::io::Result`.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A minimal stand-in for `std::io::Read`, with only the one method
+    /// the wire layer actually calls.
+    pub trait Read {
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<()>;
+    }
+
+    /// A minimal stand-in for `std::io::Write`, with only the one method
+    /// the wire layer actually calls.
+    pub trait Write {
+        fn write_all(&mut self, buf: &[u8]) -> Result<()>;
+    }
+
+    impl Write for Vec<u8> {
+        fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+            self.extend_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    /// A minimal stand-in for `std::io::Cursor`, wrapping a byte slice
+    /// (or anything that derefs to one) and tracking how far into it has
+    /// been read.
+    pub struct Cursor<T> {
+        inner: T,
+        position: usize,
+    }
+
+    impl<T: AsRef<[u8]>> Cursor<T> {
+        pub fn new(inner: T) -> Self {
+            Self { inner, position: 0 }
+        }
+    }
+
+    impl<T: AsRef<[u8]>> Read for Cursor<T> {
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+            let slice = self.inner.as_ref();
+            let end = self.position + buf.len();
+
+            if end > slice.len() {
+                return Err(Error);
+            }
+
+            buf.copy_from_slice(&slice[self.position .. end]);
+            self.position = end;
+            Ok(())
+        }
+    }
+}
+
+pub use imp::*;
@@ -0,0 +1,17 @@
+//! A parser and encoder for the DNS wire format, plus the character-string
+//! and base64/hex codecs used to render RDATA in zone-file presentation
+//! form.
+//!
+//! Builds against `std` by default. Disabling the `std` feature switches
+//! `io` over to a `no_std` + `alloc` shim, for embedded or sandboxed
+//! resolvers that can't depend on `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+pub mod io;
+pub mod presentation;
+pub mod record;
+pub mod wire;
@@ -0,0 +1,222 @@
+//! Base64 and hex presentation codecs for binary RDATA.
+//!
+//! TXT’s RDATA is text, so `record::txt` has its own quoting-and-escaping
+//! presentation form. Records that carry opaque binary RDATA — `DNSKEY`,
+//! `DS`, `TLSA`, and friends — are instead rendered the way zone files and
+//! `dig` render them: as base64 or hex, often wrapped across lines. This
+//! module provides that codec as free functions over byte slices. No
+//! record type in `record` implements these binary records, and nothing
+//! in the crate calls this module from a record type.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+
+
+/// The column width zone-file generators conventionally wrap long
+/// base64/hex blobs at — the same 78-character soft limit mail encoders
+/// use.
+pub const DEFAULT_WRAP_WIDTH: usize = 78;
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+
+/// Something that went wrong decoding a presentation-form binary blob.
+#[derive(PartialEq, Debug)]
+pub enum PresentationError {
+
+    /// The input contained a character that isn’t part of the codec’s
+    /// alphabet (ignoring whitespace, which is always skipped).
+    InvalidCharacter,
+
+    /// The input’s length didn’t divide evenly into this codec’s chunks
+    /// (an odd number of hex digits, or a base64 string that isn’t padded
+    /// out to a multiple of 4).
+    WrongLength,
+}
+
+impl fmt::Display for PresentationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter  => write!(f, "Invalid character in presentation-form input"),
+            Self::WrongLength       => write!(f, "Wrong length for presentation-form input"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PresentationError {}
+
+
+/// Encodes a byte slice as standard (RFC 4648) base64.
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(BASE64_ALPHABET[usize::from(b0 >> 2)] as char);
+        out.push(BASE64_ALPHABET[usize::from((b0 << 4 | b1 >> 4) & 0x3f)] as char);
+        out.push(if chunk.len() > 1 { BASE64_ALPHABET[usize::from((b1 << 2 | b2 >> 6) & 0x3f)] as char } else { '=' });
+        out.push(if chunk.len() > 2 { BASE64_ALPHABET[usize::from(b2 & 0x3f)] as char } else { '=' });
+    }
+
+    out
+}
+
+/// Decodes a standard (RFC 4648) base64 string back into bytes, ignoring
+/// any whitespace (such as the line-wrapping `wrap` adds).
+pub fn from_base64(s: &str) -> Result<Vec<u8>, PresentationError> {
+    let cleaned = s.bytes().filter(|b| !b.is_ascii_whitespace()).collect::<Vec<_>>();
+
+    if cleaned.len() % 4 != 0 {
+        return Err(PresentationError::WrongLength);
+    }
+
+    // `=` may only appear as 1 or 2 trailing padding bytes in the final
+    // quantum; anywhere else (embedded, more than 2, not at the very end)
+    // is invalid rather than something to silently strip.
+    if let Some(first_pad) = cleaned.iter().position(|&b| b == b'=') {
+        let padding = &cleaned[first_pad ..];
+        if padding.len() > 2 || !padding.iter().all(|&b| b == b'=') {
+            return Err(PresentationError::InvalidCharacter);
+        }
+    }
+
+    let mut buf = 0_u32;
+    let mut bits = 0_u32;
+    let mut out = Vec::with_capacity(cleaned.len() / 4 * 3);
+
+    for byte in cleaned.into_iter().filter(|b| *b != b'=') {
+        let value = BASE64_ALPHABET.iter().position(|&c| c == byte).ok_or(PresentationError::InvalidCharacter)? as u32;
+        buf = (buf << 6) | value;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buf >> bits) as u8);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Encodes a byte slice as lowercase hex.
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+
+    for byte in bytes {
+        out.push(char::from_digit(u32::from(byte >> 4), 16).unwrap());
+        out.push(char::from_digit(u32::from(byte & 0xf), 16).unwrap());
+    }
+
+    out
+}
+
+/// Decodes a hex string (case-insensitive) back into bytes, ignoring any
+/// whitespace.
+pub fn from_hex(s: &str) -> Result<Vec<u8>, PresentationError> {
+    let digits = s.chars().filter(|c| !c.is_whitespace()).collect::<Vec<_>>();
+
+    if digits.len() % 2 != 0 {
+        return Err(PresentationError::WrongLength);
+    }
+
+    digits.chunks(2).map(|pair| {
+        let hi = pair[0].to_digit(16).ok_or(PresentationError::InvalidCharacter)?;
+        let lo = pair[1].to_digit(16).ok_or(PresentationError::InvalidCharacter)?;
+        Ok((hi * 16 + lo) as u8)
+    }).collect()
+}
+
+/// Wraps an encoded blob across lines at `width` columns, the way zone
+/// files break up long base64/hex RDATA. A `width` of `0` means “don’t
+/// wrap”, and returns the input unchanged, rather than panicking.
+pub fn wrap(encoded: &str, width: usize) -> String {
+    if width == 0 {
+        return encoded.chars().collect();
+    }
+
+    let chars = encoded.chars().collect::<Vec<_>>();
+
+    chars.chunks(width)
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn base64_round_trips() {
+        let bytes = b"the quick brown fox jumps over the lazy dog";
+        assert_eq!(from_base64(&to_base64(bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn base64_matches_known_vector() {
+        assert_eq!(to_base64(b"any carnal pleasure."), "YW55IGNhcm5hbCBwbGVhc3VyZS4=");
+    }
+
+    #[test]
+    fn base64_rejects_unpadded_input() {
+        assert_eq!(from_base64("QQ"), Err(PresentationError::WrongLength));
+    }
+
+    #[test]
+    fn base64_rejects_embedded_padding() {
+        assert_eq!(from_base64("A=AA"), Err(PresentationError::InvalidCharacter));
+    }
+
+    #[test]
+    fn base64_rejects_all_padding() {
+        assert_eq!(from_base64("===="), Err(PresentationError::InvalidCharacter));
+    }
+
+    #[test]
+    fn base64_rejects_padding_before_the_final_quantum() {
+        assert_eq!(from_base64("AA==AA=="), Err(PresentationError::InvalidCharacter));
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let bytes = b"\x00\x01\xfe\xff\x7f";
+        assert_eq!(from_hex(&to_hex(bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn hex_matches_known_vector() {
+        assert_eq!(to_hex(&[0xde, 0xad, 0xbe, 0xef]), "deadbeef");
+    }
+
+    #[test]
+    fn hex_rejects_odd_length() {
+        assert_eq!(from_hex("abc"), Err(PresentationError::WrongLength));
+    }
+
+    #[test]
+    fn hex_rejects_invalid_characters() {
+        assert_eq!(from_hex("zz"), Err(PresentationError::InvalidCharacter));
+    }
+
+    #[test]
+    fn wrap_breaks_at_the_given_width() {
+        assert_eq!(wrap("abcdefghij", 4), "abcd\nefgh\nij");
+    }
+
+    #[test]
+    fn wrap_with_zero_width_returns_the_input_unwrapped() {
+        assert_eq!(wrap("abcdefghij", 0), "abcdefghij");
+    }
+
+    #[test]
+    fn wrap_default_width_matches_mail_encoders() {
+        assert_eq!(DEFAULT_WRAP_WIDTH, 78);
+    }
+}
@@ -0,0 +1,5 @@
+//! Record types, each implementing `wire::Wire` for its own RDATA.
+
+pub mod txt;
+
+pub use self::txt::TXT;
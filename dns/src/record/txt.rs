@@ -1,3 +1,8 @@
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, string::ToString, vec::Vec};
+
+use crate::io::Write;
+
 use log::*;
 
 use crate::wire::*;
@@ -7,8 +12,14 @@ use crate::wire::*;
 ///
 /// # Encoding
 ///
-/// The text encoding is not specified, but this crate treats it as UTF-8.
-/// Invalid bytes are turned into the replacement character.
+/// A TXT record’s RDATA is a sequence of independent
+/// `<character-string>`s (RFC 1035 §3.3.14), each up to 255 bytes long,
+/// rather than one flattened blob — a 255-byte string is not a signal to
+/// keep reading, it is simply the longest a single string can be.
+///
+/// The text encoding of each string is not specified, but this crate
+/// treats it as UTF-8. Invalid bytes are turned into the replacement
+/// character.
 ///
 /// # References
 ///
@@ -16,8 +27,75 @@ use crate::wire::*;
 #[derive(PartialEq, Debug)]
 pub struct TXT {
 
-    /// The message contained in the record.
-    pub message: String,
+    /// The character-strings contained in the record, in order.
+    pub messages: Vec<String>,
+}
+
+impl TXT {
+
+    /// Joins every character-string in this record into one `String`, for
+    /// callers that just want the old single-string view and don’t care
+    /// where the RDATA was split.
+    pub fn message(&self) -> String {
+        self.messages.concat()
+    }
+
+    /// Renders this record’s character-strings in RFC 1035 master-file
+    /// presentation form: each one quoted and separated by a space, with
+    /// `"`, `\`, and non-printable bytes escaped by `escape_character_string`.
+    pub fn to_presentation(&self) -> String {
+        self.messages.iter()
+            .map(|m| format!("\"{}\"", escape_character_string(m)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Escapes a character-string’s contents for presentation form: printable
+/// ASCII passes through, `"` and `\` are backslash-escaped, and any other
+/// byte is written as a three-digit `\DDD` decimal escape — the same
+/// scheme `dig` and zone files use for TXT content.
+fn escape_character_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+
+    for byte in s.bytes() {
+        match byte {
+            b'"'              => out.push_str("\\\""),
+            b'\\'             => out.push_str("\\\\"),
+            0x20 ..= 0x7e     => out.push(byte as char),
+            _                 => out.push_str(&format!("\\{:03}", byte)),
+        }
+    }
+
+    out
+}
+
+/// The inverse of `escape_character_string`: reads a presentation-form
+/// character-string (with any surrounding quotes already stripped) and
+/// returns the raw bytes it represents, unescaping `\"`, `\\`, and `\DDD`
+/// decimal escapes.
+pub fn unescape_character_string(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() && bytes[i + 1 ..= i + 3].iter().all(u8::is_ascii_digit) {
+            let decimal = core::str::from_utf8(&bytes[i + 1 ..= i + 3]).unwrap();
+            out.push(decimal.parse::<u8>().unwrap_or(b'?'));
+            i += 4;
+        }
+        else if bytes[i] == b'\\' && i + 1 < bytes.len() {
+            out.push(bytes[i + 1]);
+            i += 2;
+        }
+        else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    out
 }
 
 impl Wire for TXT {
@@ -26,50 +104,66 @@ impl Wire for TXT {
 
     #[cfg_attr(all(test, feature = "with_mutagen"), ::mutagen::mutate)]
     fn read(len: u16, c: &mut Cursor<&[u8]>) -> Result<Self, WireError> {
-        let mut buf = Vec::new();
+        let mut messages = Vec::new();
         let mut total_len = 0_u16;
 
-        loop {
+        while total_len < len {
             let next_len = c.read_u8()?;
+            let prospective_len = u32::from(total_len) + u32::from(next_len) + 1;
+
+            if prospective_len > u32::from(len) {
+                warn!("Chunk length {:?} would overrun record length {:?} (read {:?} so far)", next_len, len, total_len);
+                return Err(WireError::WrongLabelLength { expected: len, got: prospective_len });
+            }
+
             total_len += u16::from(next_len) + 1;
-            trace!("Parsed slice length -> {:?} (total so far {:?})", next_len, total_len);
 
+            let mut buf = Vec::new();
             for _ in 0 .. next_len {
                 buf.push(c.read_u8()?);
             }
 
-            if next_len < 255 {
-                break;
-            }
-            else {
-                trace!("Got length 255, so looping");
-            }
+            let message = String::from_utf8_lossy(&buf).to_string();
+            trace!("Parsed character-string -> {:?} (total so far {:?})", message, total_len);
+            messages.push(message);
         }
 
-        if len == total_len {
-            debug!("Length matches expected");
-        }
-        else {
-            warn!("Expected length {} but read {} bytes", len, buf.len());
-        }
+        // Every iteration above either returns early when a chunk would
+        // overrun `len`, or advances `total_len` to exactly the checked
+        // `prospective_len` — so the loop can only exit with `total_len == len`.
+        debug_assert_eq!(total_len, len);
+        trace!("Length is correct");
+        Ok(Self { messages })
+    }
 
-        let message = String::from_utf8_lossy(&buf).to_string();
-        trace!("Parsed message -> {:?}", message);
+    #[cfg_attr(all(test, feature = "with_mutagen"), ::mutagen::mutate)]
+    fn write(&self, w: &mut dyn Write) -> Result<usize, WireError> {
+        let mut total_len = 0_usize;
 
-        if len == total_len {
-            trace!("Length is correct");
-            Ok(Self { message })
-        }
-        else {
-            warn!("Length is incorrect (record length {:?}, message length {:?})", len, total_len);
-            Err(WireError::WrongLabelLength { expected: len, got: total_len })
+        for message in &self.messages {
+            let bytes = message.as_bytes();
+
+            if bytes.len() > 255 {
+                warn!("Message {:?} is {} bytes, too long for a single character-string", message, bytes.len());
+                return Err(WireError::WrongLabelLength { expected: 255, got: bytes.len() as u32 });
+            }
+
+            w.write_all(&[bytes.len() as u8])?;
+            w.write_all(bytes)?;
+            total_len += 1 + bytes.len();
         }
+
+        trace!("Wrote {:?} character-strings ({:?} bytes)", self.messages.len(), total_len);
+        Ok(total_len)
     }
 }
 
 
 #[cfg(test)]
 mod test {
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
     use super::*;
 
     #[test]
@@ -81,61 +175,122 @@ mod test {
 
         assert_eq!(TXT::read(buf.len() as _, &mut Cursor::new(buf)).unwrap(),
                    TXT {
-                       message: String::from("txt me"),
+                       messages: vec![ String::from("txt me") ],
                    });
     }
 
     #[test]
-    fn parses_two_iterations() {
+    fn parses_two_separate_strings() {
         let buf = &[
-            0xFF,  // message chunk length
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41, 0x41,
-            0x41, 0x41,  // exactly two hundred and fifty five ‘A’s (screaming)
-            0x04,  // message chunk length
-            0x41, 0x41, 0x41, 0x41,  // four more ‘A’s (the scream abruptly stops)
+            0x06,  // first string length
+            0x74, 0x78, 0x74, 0x20, 0x6d, 0x65,  // first string
+            0x04,  // second string length
+            0x6d, 0x6f, 0x72, 0x65,  // second string
         ];
 
         assert_eq!(TXT::read(buf.len() as _, &mut Cursor::new(buf)).unwrap(),
                    TXT {
-                       message: String::from("AAAAAAAAAAAAAAAAAAAAAAAAAAAAA\
-                                              AAAAAAAAAAAAAAAAAAAAAAAAAAAAA\
-                                              AAAAAAAAAAAAAAAAAAAAAAAAAAAAA\
-                                              AAAAAAAAAAAAAAAAAAAAAAAAAAAAA\
-                                              AAAAAAAAAAAAAAAAAAAAAAAAAAAAA\
-                                              AAAAAAAAAAAAAAAAAAAAAAAAAAAAA\
-                                              AAAAAAAAAAAAAAAAAAAAAAAAAAAAA\
-                                              AAAAAAAAAAAAAAAAAAAAAAAAAAAAA\
-                                              AAAAAAAAAAAAAAAAAAAAAAAAAAA"),
+                       messages: vec![ String::from("txt me"), String::from("more") ],
                    });
-        // did you know you can just _write_ code like this, and nobody will stop you?
+    }
+
+    #[test]
+    fn a_255_byte_string_is_not_a_continuation() {
+        let mut buf = vec![ 0xFF ];
+        buf.extend(core::iter::repeat_n(0x41, 255));  // a full 255-byte string
+        buf.push(0x04);  // a second, unrelated string
+        buf.extend(core::iter::repeat_n(0x42, 4));
+
+        let txt = TXT::read(buf.len() as _, &mut Cursor::new(&buf)).unwrap();
+
+        assert_eq!(txt.messages.len(), 2);
+        assert_eq!(txt.messages[0], "A".repeat(255));
+        assert_eq!(txt.messages[1], "BBBB");
+    }
+
+    #[test]
+    fn message_joins_every_string() {
+        let txt = TXT { messages: vec![ String::from("txt "), String::from("me") ] };
+        assert_eq!(txt.message(), "txt me");
     }
 
     #[test]
     fn record_empty() {
-        assert_eq!(TXT::read(0, &mut Cursor::new(&[])),
-                   Err(WireError::IO));
+        assert_eq!(TXT::read(0, &mut Cursor::new(&[])).unwrap(),
+                   TXT { messages: vec![] });
+    }
+
+    #[test]
+    fn write_round_trips() {
+        let txt = TXT { messages: vec![ String::from("txt me") ] };
+
+        let mut buf = Vec::new();
+        let len = txt.write(&mut buf).unwrap();
+
+        assert_eq!(len, buf.len());
+        assert_eq!(TXT::read(buf.len() as _, &mut Cursor::new(&buf)).unwrap(), txt);
+    }
+
+    #[test]
+    fn write_rejects_overlong_messages() {
+        let txt = TXT { messages: vec![ "a".repeat(300) ] };
+
+        let mut buf = Vec::new();
+        assert_eq!(txt.write(&mut buf),
+                   Err(WireError::WrongLabelLength { expected: 255, got: 300 }));
+    }
+
+    #[test]
+    fn write_round_trips_an_empty_record() {
+        let txt = TXT { messages: vec![] };
+
+        let mut buf = Vec::new();
+        let len = txt.write(&mut buf).unwrap();
+
+        assert_eq!(len, 0);
+        assert_eq!(TXT::read(buf.len() as _, &mut Cursor::new(&buf)).unwrap(), txt);
+    }
+
+    #[test]
+    fn write_round_trips_an_empty_string() {
+        let txt = TXT { messages: vec![ String::new() ] };
+
+        let mut buf = Vec::new();
+        let len = txt.write(&mut buf).unwrap();
+
+        assert_eq!(len, buf.len());
+        assert_eq!(TXT::read(buf.len() as _, &mut Cursor::new(&buf)).unwrap(), txt);
+    }
+
+    #[test]
+    fn presentation_passes_printable_ascii_through() {
+        let txt = TXT { messages: vec![ String::from("txt me") ] };
+        assert_eq!(txt.to_presentation(), "\"txt me\"");
+    }
+
+    #[test]
+    fn presentation_escapes_quotes_and_backslashes() {
+        let txt = TXT { messages: vec![ String::from("say \"hi\\bye\"") ] };
+        assert_eq!(txt.to_presentation(), "\"say \\\"hi\\\\bye\\\"\"");
+    }
+
+    #[test]
+    fn presentation_escapes_non_printable_bytes() {
+        let txt = TXT { messages: vec![ String::from("\u{7f}") ] };
+        assert_eq!(txt.to_presentation(), "\"\\127\"");
+    }
+
+    #[test]
+    fn presentation_joins_multiple_strings_with_a_space() {
+        let txt = TXT { messages: vec![ String::from("foo"), String::from("bar") ] };
+        assert_eq!(txt.to_presentation(), "\"foo\" \"bar\"");
+    }
+
+    #[test]
+    fn unescape_reverses_escape() {
+        let original = "say \"hi\\bye\" \u{7f} done";
+        let escaped = escape_character_string(original);
+        assert_eq!(unescape_character_string(&escaped), original.as_bytes());
     }
 
     #[test]
@@ -147,4 +302,33 @@ mod test {
         assert_eq!(TXT::read(23, &mut Cursor::new(buf)),
                    Err(WireError::IO));
     }
+
+    #[test]
+    fn chunk_length_overruns_record_length() {
+        let buf = &[
+            0x06,  // message chunk length (claims 6 bytes)
+            0x74, 0x78, 0x74, 0x20, 0x6d, 0x65,  // message chunk
+        ];
+
+        // the record only claims to be 4 bytes long, so the 6-byte chunk
+        // (plus its length byte) would overrun it
+        assert_eq!(TXT::read(4, &mut Cursor::new(buf)),
+                   Err(WireError::WrongLabelLength { expected: 4, got: 7 }));
+    }
+
+    #[test]
+    fn trailing_bytes_inside_record() {
+        let buf = &[
+            0x06,  // message chunk length
+            0x74, 0x78, 0x74, 0x20, 0x6d, 0x65,  // message chunk
+            0xff, 0xff,  // trailing bytes that belong to another record
+        ];
+
+        // the record claims to be 9 bytes long, so after the first
+        // 7-byte character-string there are 2 bytes left to account for;
+        // those are read as the start of a second character-string,
+        // whose claimed length (255) would overrun the record
+        assert_eq!(TXT::read(9, &mut Cursor::new(buf)),
+                   Err(WireError::WrongLabelLength { expected: 9, got: 263 }));
+    }
 }
@@ -0,0 +1,103 @@
+//! Reading and writing DNS packets at the byte level.
+//!
+//! Every record type in `record` implements the `Wire` trait defined here,
+//! which pairs a `read` (decode from the wire format found in a DNS
+//! message) with a `write` (encode back into that same format). Both are
+//! written against the `io` module’s abstraction rather than `std::io`
+//! directly, so they also work in `no_std` + `alloc` builds.
+
+use core::fmt;
+
+use crate::io::{self, Read, Write};
+
+pub use crate::io::Cursor;
+
+
+/// A type that can be read from, and written to, the wire format used by
+/// DNS messages.
+///
+/// Implementors receive the RDATA length taken from the record’s header
+/// (`len`) and a cursor positioned at the start of the RDATA, and must
+/// consume exactly `len` bytes. `write` is the inverse: it appends the
+/// record’s RDATA to `w` and returns the number of bytes written, so the
+/// caller can go back and fill in the RR header’s length field.
+pub trait Wire: Sized {
+
+    /// This record type’s name, such as `"TXT"` or `"A"`.
+    const NAME: &'static str;
+
+    /// This record type’s `TYPE` value, as assigned by IANA.
+    const RR_TYPE: u16;
+
+    /// Reads the RDATA for a record of this type out of the given cursor.
+    fn read(len: u16, c: &mut Cursor<&[u8]>) -> Result<Self, WireError>;
+
+    /// Writes the RDATA for a record of this type to the given writer,
+    /// returning the number of bytes written.
+    fn write(&self, w: &mut dyn Write) -> Result<usize, WireError>;
+}
+
+
+/// Something that went wrong converting DNS bytes into a strongly-typed
+/// record.
+#[derive(PartialEq, Debug)]
+pub enum WireError {
+
+    /// There was an IO error reading from or writing to the buffer.
+    IO,
+
+    /// The length of a label or chunk would have caused the record to
+    /// read or write more bytes than it was supposed to. `got` is a `u32`
+    /// so it can report lengths larger than `u16::MAX` without truncating
+    /// (e.g. a chunk length plus what's already been read overrunning a
+    /// `u16`-bounded record length, or a caller-constructed message that's
+    /// longer than a single character-string can be).
+    WrongLabelLength { expected: u16, got: u32 },
+}
+
+impl From<io::Error> for WireError {
+    fn from(_error: io::Error) -> Self {
+        Self::IO
+    }
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IO                                  => write!(f, "IO error"),
+            Self::WrongLabelLength { expected, got }  => write!(f, "Wrong label length (expected {}, got {})", expected, got),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WireError {}
+
+
+/// Extension methods for reading big-endian integers out of a byte cursor,
+/// used by every `Wire::read` implementation.
+pub trait CursorExt {
+    fn read_u8(&mut self) -> io::Result<u8>;
+    fn read_u16_be(&mut self) -> io::Result<u16>;
+    fn read_u32_be(&mut self) -> io::Result<u32>;
+}
+
+impl CursorExt for Cursor<&[u8]> {
+    fn read_u8(&mut self) -> io::Result<u8> {
+        let mut buf = [0_u8; 1];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn read_u16_be(&mut self) -> io::Result<u16> {
+        let mut buf = [0_u8; 2];
+        self.read_exact(&mut buf)?;
+        Ok(u16::from_be_bytes(buf))
+    }
+
+    fn read_u32_be(&mut self) -> io::Result<u32> {
+        let mut buf = [0_u8; 4];
+        self.read_exact(&mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+}